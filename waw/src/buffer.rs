@@ -0,0 +1,178 @@
+use enum_map::{Enum, EnumArray, EnumMap};
+use js_sys::{Array, Float32Array};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::types::{AutomationRate, ParameterDescriptor};
+
+/// Number of samples processed per `process()` call.
+const RENDER_QUANTUM_FRAMES: usize = 128;
+
+/// Owns the per-channel sample storage copied to/from the JS arrays of `Float32Array`
+/// channels each `process()` call.
+pub struct AudioBuffer {
+    inputs: Vec<Vec<[f32; RENDER_QUANTUM_FRAMES]>>,
+    outputs: Vec<Vec<[f32; RENDER_QUANTUM_FRAMES]>>,
+}
+
+impl AudioBuffer {
+    pub fn new(
+        number_of_inputs: usize,
+        channel_count: usize,
+        number_of_outputs: usize,
+        output_channel_count: usize,
+    ) -> Self {
+        Self {
+            inputs: vec![vec![[0.0; RENDER_QUANTUM_FRAMES]; channel_count]; number_of_inputs],
+            outputs: vec![
+                vec![[0.0; RENDER_QUANTUM_FRAMES]; output_channel_count];
+                number_of_outputs
+            ],
+        }
+    }
+
+    /// Copies the JS `inputs` array of per-channel `Float32Array`s into Rust-owned
+    /// storage.
+    pub(crate) fn copy_from_input(&mut self, input: &Array) {
+        for (i, channels) in self.inputs.iter_mut().enumerate() {
+            let Some(js_channels) = input.get(i as u32).dyn_into::<Array>().ok() else {
+                continue;
+            };
+            for (c, samples) in channels.iter_mut().enumerate() {
+                if let Some(js_samples) = js_channels.get(c as u32).dyn_into::<Float32Array>().ok()
+                {
+                    js_samples.copy_to(samples);
+                }
+            }
+        }
+    }
+
+    /// Copies Rust-owned output storage back into the JS `outputs` array of per-channel
+    /// `Float32Array`s.
+    pub(crate) fn copy_to_output(&self, output: &Array) {
+        for (i, channels) in self.outputs.iter().enumerate() {
+            let Some(js_channels) = output.get(i as u32).dyn_into::<Array>().ok() else {
+                continue;
+            };
+            for (c, samples) in channels.iter().enumerate() {
+                if let Some(js_samples) = js_channels.get(c as u32).dyn_into::<Float32Array>().ok()
+                {
+                    js_samples.copy_from(samples);
+                }
+            }
+        }
+    }
+
+    /// Samples for input `index`, one slice per channel.
+    pub fn input(&self, index: usize) -> &[[f32; RENDER_QUANTUM_FRAMES]] {
+        &self.inputs[index]
+    }
+
+    /// Mutable samples for output `index`, one slice per channel.
+    pub fn output_mut(&mut self, index: usize) -> &mut [[f32; RENDER_QUANTUM_FRAMES]] {
+        &mut self.outputs[index]
+    }
+}
+
+/// Render-quantum storage for a single parameter's values.
+///
+/// An a-rate parameter carries one value per sample (the full render quantum); a k-rate
+/// parameter carries a single value held constant across the quantum.
+#[derive(Debug, Clone)]
+pub enum Param {
+    ARate(Box<[f32; RENDER_QUANTUM_FRAMES]>),
+    KRate(f32),
+}
+
+impl Default for Param {
+    fn default() -> Self {
+        Param::KRate(0.0)
+    }
+}
+
+impl Param {
+    fn from_automation_rate(rate: AutomationRate, default_value: f32) -> Self {
+        match rate {
+            AutomationRate::ARate => {
+                Param::ARate(Box::new([default_value; RENDER_QUANTUM_FRAMES]))
+            }
+            AutomationRate::KRate => Param::KRate(default_value),
+        }
+    }
+
+    /// The k-rate scalar, or the first sample of an a-rate buffer.
+    pub fn as_scalar(&self) -> f32 {
+        match self {
+            Param::ARate(values) => values[0],
+            Param::KRate(value) => *value,
+        }
+    }
+
+    /// The full a-rate per-sample slice, or `None` for a k-rate parameter.
+    pub fn as_slice(&self) -> Option<&[f32]> {
+        match self {
+            Param::ARate(values) => Some(values.as_slice()),
+            Param::KRate(_) => None,
+        }
+    }
+}
+
+/// Per-render-quantum parameter values, indexed by the worklet's `Param` enum.
+pub struct ParamBuffer<P: EnumArray<Param>> {
+    values: EnumMap<P, Param>,
+}
+
+impl<P> Default for ParamBuffer<P>
+where
+    P: EnumArray<Param> + ParameterDescriptor,
+{
+    fn default() -> Self {
+        Self {
+            values: EnumMap::from_fn(|variant: P| {
+                Param::from_automation_rate(variant.automation_rate(), variant.default_value())
+            }),
+        }
+    }
+}
+
+impl<P> ParamBuffer<P>
+where
+    P: EnumArray<Param> + ParameterDescriptor + Enum,
+{
+    /// Returns this parameter's current render-quantum value: an a-rate per-sample
+    /// slice, or a k-rate scalar, depending on its descriptor.
+    pub fn get(&self, param: P) -> &Param {
+        &self.values[param]
+    }
+
+    /// Copies the JS `parameters` object (one `Float32Array` per parameter name) into
+    /// the matching a-rate/k-rate slot.
+    ///
+    /// The Web Audio spec lets the host hand back a length-1 array for an a-rate param
+    /// when no automation events were scheduled this quantum (the value is constant
+    /// over the 128 samples); that value is broadcast across the slice rather than
+    /// assumed to already be full-length, which `Float32Array::copy_to` would otherwise
+    /// panic on.
+    pub(crate) fn copy_from_params(&mut self, parameters: &JsValue) {
+        for i in 0..P::LENGTH {
+            let variant = P::from_usize(i);
+            let js_values = js_sys::Reflect::get(parameters, &variant.name().into())
+                .ok()
+                .and_then(|value| value.dyn_into::<Float32Array>().ok());
+
+            let Some(js_values) = js_values else {
+                continue;
+            };
+
+            match &mut self.values[variant] {
+                Param::ARate(values) => {
+                    if js_values.length() as usize == values.len() {
+                        js_values.copy_to(values.as_mut_slice());
+                    } else {
+                        values.fill(js_values.get_index(0));
+                    }
+                }
+                Param::KRate(value) => *value = js_values.get_index(0),
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! waw — build Web Audio `AudioWorkletProcessor`s in Rust, compiled to wasm.
+
+pub mod buffer;
+pub mod ring_buffer;
+pub mod rpc;
+pub mod types;
+mod utils;
+pub mod worker_pool;
+pub mod worklet;
+
+pub use worklet::{current_frame, current_time, sample_rate, AudioModule, Emitter};
@@ -0,0 +1,130 @@
+use js_sys::{Atomics, Int32Array, SharedArrayBuffer, Uint8Array};
+
+/// Lock-free single-producer/single-consumer ring buffer over a `SharedArrayBuffer`.
+///
+/// The buffer is laid out as two `Int32Array` slots (read index, write index) followed
+/// by the byte region used to store length-prefixed frames. Indices are manipulated with
+/// `Atomics.load`/`Atomics.store` so the main thread and the audio thread can exchange
+/// frames without crossing the `postMessage` queue.
+#[derive(Clone)]
+pub struct RingBuffer {
+    bytes: Uint8Array,
+    indices: Int32Array,
+    capacity: u32,
+}
+
+const READ_INDEX: u32 = 0;
+const WRITE_INDEX: u32 = 1;
+
+impl RingBuffer {
+    /// Number of bytes reserved for the two index slots at the head of the buffer.
+    const HEADER_BYTES: u32 = 8;
+
+    /// Wraps a `SharedArrayBuffer` previously allocated for this transport (typically via
+    /// [`Self::allocate`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero (a zero-length ring can never hold a frame), or if
+    /// `sab` isn't at least [`Self::HEADER_BYTES`] + `capacity` bytes long with a byte
+    /// length that's a multiple of 4 — required by `Int32Array::new`, and only
+    /// guaranteed if `sab` was sized by [`Self::allocate`].
+    pub fn new(sab: &SharedArrayBuffer, capacity: u32) -> Self {
+        assert!(
+            capacity > 0,
+            "waw: ring buffer capacity must be greater than zero"
+        );
+        assert!(
+            sab.byte_length() % 4 == 0,
+            "waw: ring buffer's SharedArrayBuffer must have a byte length that's a \
+             multiple of 4 (did you allocate it with RingBuffer::allocate?)"
+        );
+        assert!(
+            sab.byte_length() >= Self::HEADER_BYTES + capacity,
+            "waw: ring buffer's SharedArrayBuffer is smaller than HEADER_BYTES + capacity"
+        );
+
+        Self {
+            bytes: Uint8Array::new_with_byte_offset_and_length(
+                sab,
+                Self::HEADER_BYTES,
+                capacity,
+            ),
+            indices: Int32Array::new(sab),
+            capacity,
+        }
+    }
+
+    /// Allocates a fresh `SharedArrayBuffer` sized for at least `capacity` bytes of frame
+    /// data, padded so the total byte length is a multiple of 4 as `Int32Array::new`
+    /// requires.
+    ///
+    /// Called from the node (main-thread) side during construction, before the
+    /// `SharedArrayBuffer` is handed to the `AudioWorkletNode` as the `ringBuffer` or
+    /// `eventRingBuffer` key of its options object, which is where `WorkletOptions` reads
+    /// it from (not from `processorOptions`); there is no Rust main-thread wrapper in
+    /// this crate, so JS or a consumer's own `web_sys`-based setup code calls this
+    /// directly.
+    pub fn allocate(capacity: u32) -> SharedArrayBuffer {
+        assert!(
+            capacity > 0,
+            "waw: ring buffer capacity must be greater than zero"
+        );
+        let padded_capacity = (capacity + 3) & !3;
+        SharedArrayBuffer::new(Self::HEADER_BYTES + padded_capacity)
+    }
+
+    fn load(&self, index: u32) -> u32 {
+        Atomics::load(&self.indices, index).unwrap_or(0) as u32
+    }
+
+    /// Writes a length-prefixed frame into the ring, wrapping at capacity.
+    ///
+    /// Returns `false` without writing anything if `frame` would overrun the reader.
+    pub fn try_write(&self, frame: &[u8]) -> bool {
+        let read = self.load(READ_INDEX);
+        let write = self.load(WRITE_INDEX);
+        let used = write.wrapping_sub(read);
+        let needed = 4 + frame.len() as u32;
+        if used + needed > self.capacity {
+            return false;
+        }
+
+        let mut cursor = write % self.capacity;
+        for byte in frame.len().to_le_bytes().iter().take(4).chain(frame) {
+            self.bytes.set_index(cursor, *byte);
+            cursor = (cursor + 1) % self.capacity;
+        }
+
+        Atomics::store(&self.indices, WRITE_INDEX, (write + needed) as i32).unwrap();
+        Atomics::notify(&self.indices, WRITE_INDEX).ok();
+        true
+    }
+
+    /// Polls for a complete frame and, if one is available, copies it out and advances
+    /// the read index. Intended to be called once per `process()` render quantum.
+    pub fn try_read(&self) -> Option<Vec<u8>> {
+        let read = self.load(READ_INDEX);
+        let write = self.load(WRITE_INDEX);
+        if write.wrapping_sub(read) < 4 {
+            return None;
+        }
+
+        let mut cursor = read % self.capacity;
+        let mut len_bytes = [0u8; 4];
+        for byte in len_bytes.iter_mut() {
+            *byte = self.bytes.get_index(cursor);
+            cursor = (cursor + 1) % self.capacity;
+        }
+        let len = u32::from_le_bytes(len_bytes);
+
+        let mut frame = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            frame.push(self.bytes.get_index(cursor));
+            cursor = (cursor + 1) % self.capacity;
+        }
+
+        Atomics::store(&self.indices, READ_INDEX, (read + 4 + len) as i32).unwrap();
+        Some(frame)
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{MessageEvent, MessagePort};
+
+use crate::types::InternalMessage;
+
+/// Tracks in-flight request/response pairs on the node (main-thread) side, keyed by a
+/// monotonically increasing correlation id.
+///
+/// `request` tags an outbound command with a fresh id and returns a `Promise` that
+/// resolves once the worklet's matching `Emitter::respond` reply arrives; callers no
+/// longer need to hand-roll id matching themselves.
+#[derive(Clone, Default)]
+pub struct PendingRequests {
+    next_id: Arc<AtomicU64>,
+    pending: Arc<Mutex<HashMap<u64, Function>>>,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a `PendingRequests` and wires it up to `port`'s `message` event: every
+    /// `InternalMessage::Reply` that arrives resolves its matching pending request. The
+    /// listener `Closure` is leaked (`Closure::forget`) so it stays registered for the
+    /// lifetime of the port; `Processor::connect` instead keeps its own message callback
+    /// alive in a field of the `Processor`.
+    pub fn attach(port: &MessagePort) -> Self {
+        let pending_requests = Self::new();
+
+        let resolver = pending_requests.clone();
+        let callback = Closure::wrap(Box::new(move |event: MessageEvent| {
+            if let Ok(InternalMessage::Reply { id }) =
+                serde_wasm_bindgen::from_value::<InternalMessage>(event.data())
+            {
+                let reply = Reflect::get(&event.data(), &"event".into()).unwrap_or(JsValue::UNDEFINED);
+                resolver.resolve(id, &reply);
+            }
+        }) as Box<dyn Fn(MessageEvent)>);
+
+        port.add_event_listener_with_callback("message", callback.as_ref().unchecked_ref())
+            .ok();
+        callback.forget();
+
+        pending_requests
+    }
+
+    /// Posts `command` to `port` tagged with a fresh correlation id and returns a
+    /// `Promise` that resolves with the worklet's reply event once it arrives.
+    pub fn request(&self, port: &MessagePort, command: JsValue) -> Promise {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let pending = self.pending.clone();
+
+        let promise = Promise::new(&mut |resolve, _reject| {
+            pending.lock().unwrap().insert(id, resolve);
+        });
+
+        let envelope = serde_wasm_bindgen::to_value(&InternalMessage::Request { id })
+            .expect("InternalMessage always serializes");
+        Reflect::set(&envelope, &"command".into(), &command).ok();
+        port.post_message(&envelope).ok();
+
+        promise
+    }
+
+    /// Resolves the pending request matching `id`, if one is still waiting. Called
+    /// automatically for ports set up via [`Self::attach`]; exposed for callers wiring
+    /// their own `message` listener instead.
+    pub fn resolve(&self, id: u64, reply: &JsValue) {
+        if let Some(resolve) = self.pending.lock().unwrap().remove(&id) {
+            resolve.call1(&JsValue::UNDEFINED, reply).ok();
+        }
+    }
+}
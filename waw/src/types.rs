@@ -0,0 +1,165 @@
+use js_sys::{Reflect, SharedArrayBuffer};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::AudioWorkletNodeOptions;
+
+/// Uninhabited type used as the default `AudioModule::Event`/`Command`/`Param` for
+/// worklets that don't need one of these channels.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Never(());
+
+impl From<JsValue> for Never {
+    fn from(_: JsValue) -> Self {
+        unreachable!("Never is never constructed")
+    }
+}
+
+impl From<Never> for JsValue {
+    fn from(value: Never) -> Self {
+        match value.0 {}
+    }
+}
+
+/// Automation rate for an `AudioParam`: sampled once per render quantum ("k-rate") or
+/// once per sample ("a-rate"). Mirrors `AudioParamDescriptor.automationRate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomationRate {
+    ARate,
+    KRate,
+}
+
+impl AutomationRate {
+    fn as_js_str(self) -> &'static str {
+        match self {
+            AutomationRate::ARate => "a-rate",
+            AutomationRate::KRate => "k-rate",
+        }
+    }
+}
+
+/// Describes a single `AudioParam` exposed by a worklet's `Param` enum.
+///
+/// Implemented per-variant (by the `#[derive(ParameterDescriptor)]` macro, reading
+/// per-variant attributes); every method mirrors a field of `AudioParamDescriptor` from
+/// the Web Audio spec.
+pub trait ParameterDescriptor {
+    /// The `name` registered in `parameterDescriptors` and used to look the param up on
+    /// the processor's `AudioParamMap`.
+    fn name(&self) -> &'static str;
+
+    /// `AudioParamDescriptor.defaultValue`.
+    fn default_value(&self) -> f32 {
+        0.0
+    }
+
+    /// `AudioParamDescriptor.minValue`.
+    fn min_value(&self) -> f32 {
+        f32::MIN
+    }
+
+    /// `AudioParamDescriptor.maxValue`.
+    fn max_value(&self) -> f32 {
+        f32::MAX
+    }
+
+    /// `AudioParamDescriptor.automationRate`. Defaults to k-rate, the Web Audio spec
+    /// default for custom `AudioWorkletProcessor` params.
+    fn automation_rate(&self) -> AutomationRate {
+        AutomationRate::KRate
+    }
+
+    /// Serializes this descriptor to the JSON object literal consumed by
+    /// `static get parameterDescriptors()`.
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"name\":\"{}\",\"defaultValue\":{},\"minValue\":{},\"maxValue\":{},\"automationRate\":\"{}\"}}",
+            self.name(),
+            self.default_value(),
+            self.min_value(),
+            self.max_value(),
+            self.automation_rate().as_js_str(),
+        )
+    }
+}
+
+/// Static, per-worklet-type metadata generated by the `waw::main!` macro.
+pub trait AudioModuleDescriptor {
+    /// Name registered with `registerProcessor`.
+    fn processor_name() -> &'static str;
+
+    /// JSON array literal consumed by `static get parameterDescriptors()`, built by
+    /// joining each `Self::Param` variant's [`ParameterDescriptor::to_json`].
+    fn parameter_descriptor_json() -> String;
+}
+
+/// Internal control messages exchanged over the same `MessagePort` as user
+/// `Command`/`Event` values. Tagged by `method` so a message can be told apart from an
+/// opaque user payload by whether it deserializes as this type at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum InternalMessage {
+    Destroy,
+    /// A node-originated command expecting a correlated reply; the actual command value
+    /// is attached as a sibling `command` property rather than part of this enum, same
+    /// as plain (non-request) commands are carried as the raw message payload.
+    Request { id: u64 },
+    /// The worklet's reply to a `Request`, produced by `Emitter::respond`; the reply
+    /// event is attached as a sibling `event` property.
+    Reply { id: u64 },
+}
+
+/// Rust-side view over the (non-standard) options object manually attached to the
+/// `AudioWorkletProcessor` by the generated JS glue.
+pub struct WorkletOptions {
+    pub number_of_inputs: u32,
+    pub channel_count: u32,
+    pub number_of_outputs: u32,
+    pub output_channel_count: u32,
+    /// `SharedArrayBuffer` backing the inbound (node -> worklet) command ring, if the
+    /// node opted into the zero-copy transport.
+    pub ring_buffer: Option<SharedArrayBuffer>,
+    /// Capacity, in bytes, of the command ring's frame region.
+    pub ring_buffer_capacity: u32,
+    /// `SharedArrayBuffer` backing the outbound (worklet -> node) event ring. Kept as a
+    /// separate `SharedArrayBuffer` from `ring_buffer`: each ring is single-producer/
+    /// single-consumer, and the two directions have different producers and consumers,
+    /// so they can't share one ring without the two sides racing each other's writes.
+    pub event_ring_buffer: Option<SharedArrayBuffer>,
+    /// Capacity, in bytes, of the event ring's frame region.
+    pub event_ring_capacity: u32,
+    /// Number of worker threads to spawn for `waw::worker_pool::scope`, or `0` to skip
+    /// pool creation entirely.
+    pub pool_size: usize,
+}
+
+impl From<AudioWorkletNodeOptions> for WorkletOptions {
+    fn from(options: AudioWorkletNodeOptions) -> Self {
+        let get_u32 = |key: &str, default: u32| -> u32 {
+            Reflect::get(&options, &key.into())
+                .ok()
+                .and_then(|value| value.as_f64())
+                .map(|value| value as u32)
+                .unwrap_or(default)
+        };
+
+        let get_sab = |key: &str| -> Option<SharedArrayBuffer> {
+            Reflect::get(&options, &key.into())
+                .ok()
+                .and_then(|value| value.dyn_into::<SharedArrayBuffer>().ok())
+        };
+
+        Self {
+            number_of_inputs: get_u32("numberOfInputs", 1),
+            channel_count: get_u32("channelCount", 2),
+            number_of_outputs: get_u32("numberOfOutputs", 1),
+            output_channel_count: get_u32("outputChannelCount", 2),
+            ring_buffer: get_sab("ringBuffer"),
+            ring_buffer_capacity: get_u32("ringBufferCapacity", 4096),
+            event_ring_buffer: get_sab("eventRingBuffer"),
+            event_ring_capacity: get_u32("eventRingCapacity", 4096),
+            pool_size: get_u32("poolSize", 0) as usize,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use js_sys::{global, Reflect};
+
+/// Panics if not currently running inside an `AudioWorkletGlobalScope`.
+///
+/// Guards construction paths that rely on globals (`sampleRate`, `currentFrame`, ...)
+/// only present in that scope, so a misuse fails fast with a clear message instead of a
+/// cryptic `Reflect::get`/`unwrap` panic deeper in the call stack.
+pub fn assert_worklet() {
+    let in_worklet_scope = Reflect::get(&global(), &"AudioWorkletGlobalScope".into())
+        .map(|value| !value.is_undefined())
+        .unwrap_or(false);
+
+    assert!(
+        in_worklet_scope,
+        "waw: expected to be running inside an AudioWorkletGlobalScope"
+    );
+}
@@ -0,0 +1,11 @@
+use wasm_bindgen::prelude::*;
+
+// `import.meta.url` isn't reachable from a js-snippet module, so this is implemented as
+// a tiny standalone snippet file rather than inline Rust.
+#[wasm_bindgen(module = "/src/import_meta.js")]
+extern "C" {
+    /// The URL of the current wasm-bindgen generated JS module, as seen from wherever
+    /// this was called.
+    #[wasm_bindgen(js_name = "url")]
+    pub fn url_js() -> String;
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_rayon::init_thread_pool;
+
+/// Pool of Web Worker threads sharing the worklet's linear memory, used to parallelise
+/// data-parallel DSP work (FFT banks, convolution, polyphonic synths, ...) across
+/// `process()` calls.
+///
+/// Requires cross-origin isolation, since spawning workers that share memory depends on
+/// a `SharedArrayBuffer`-backed `WebAssembly.Memory`. The pool is created lazily by
+/// `Processor::new` from `WorkletOptions::pool_size` and reused across render quanta —
+/// spawning workers per quantum would blow the audio thread's deadline.
+pub struct WorkerPool {
+    size: usize,
+    ready: Arc<AtomicBool>,
+}
+
+static POOL: OnceLock<WorkerPool> = OnceLock::new();
+
+impl WorkerPool {
+    /// Spawns the pool on first call; later calls with a different `size` are ignored,
+    /// the pool is sized once for the lifetime of the worklet global scope.
+    pub(crate) fn init(size: usize) -> &'static WorkerPool {
+        POOL.get_or_init(|| {
+            let ready = Arc::new(AtomicBool::new(false));
+            let ready_handle = ready.clone();
+
+            // `init_thread_pool` returns a `Promise` that only resolves once the worker
+            // threads have actually attached to the shared pool; calling `rayon::scope`
+            // before then is a known hang/abort with wasm-bindgen-rayon, so readiness is
+            // tracked and `scope` refuses to run until this flips.
+            let promise = init_thread_pool(size as u32);
+            wasm_bindgen_futures::spawn_local(async move {
+                JsFuture::from(promise).await.ok();
+                ready_handle.store(true, Ordering::Release);
+            });
+
+            WorkerPool { size, ready }
+        })
+    }
+
+    /// Number of worker threads backing the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the pool's worker threads have finished attaching.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+}
+
+/// Dispatches data-parallel work across the worklet's worker pool, joining before
+/// returning.
+///
+/// This is a thin wrapper around [`rayon::scope`], which returns only once every
+/// closure spawned on `s` has finished. The join has no time bound: a `process()` call
+/// that partitions channels or frequency bins across `s.spawn(..)` closures waits for
+/// all of them, and meets the render-quantum deadline only if that work does.
+///
+/// Returns `None` without running `f` if the pool's worker threads haven't finished
+/// attaching yet (`WorkerPool::init` spawns them asynchronously, and `wasm-bindgen-rayon`
+/// hangs/aborts if `rayon::scope` is entered before they attach). Readiness only flips on
+/// a JS microtask tick, which a synchronous, audio-thread `process()` call can never wait
+/// on — so rather than spin-waiting for a transition that can't happen underneath it,
+/// callers should check for `None` and fall back to processing that render quantum
+/// serially instead.
+///
+/// # Panics
+///
+/// Panics if called before the pool has been initialised (i.e. outside a
+/// [`crate::worklet::Processor`] with `WorkletOptions::pool_size` set).
+pub fn scope<F, R>(f: F) -> Option<R>
+where
+    F: FnOnce(&rayon::Scope) -> R,
+{
+    let pool = POOL
+        .get()
+        .expect("waw: worker pool not initialised, set WorkletOptions::pool_size");
+
+    if !pool.is_ready() {
+        return None;
+    }
+
+    Some(rayon::scope(f))
+}
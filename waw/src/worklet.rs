@@ -18,6 +18,8 @@ use web_sys::{
 };
 
 use crate::buffer::AudioBuffer;
+use crate::ring_buffer::RingBuffer;
+use crate::worker_pool::WorkerPool;
 use crate::{
     buffer::{Param, ParamBuffer},
     types::{AudioModuleDescriptor, InternalMessage, Never, ParameterDescriptor, WorkletOptions},
@@ -28,6 +30,10 @@ use crate::{
 #[derive(Clone)]
 pub struct Emitter<E> {
     port: MessagePort,
+    /// When set, `send` writes outbound events to the ring buffer instead of `post_message`,
+    /// falling back to the port when the ring is full; `send_with_transfer` and `respond`
+    /// always use the port.
+    ring: Option<RingBuffer>,
     _phantom: PhantomData<E>,
 }
 
@@ -36,13 +42,68 @@ impl<E: Into<JsValue>> Emitter<E> {
     pub fn new(port: MessagePort) -> Self {
         Self {
             port,
+            ring: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Construct a new emitter backed by a `SharedArrayBuffer` ring buffer, falling back
+    /// to `port` for any frame the ring can't currently hold.
+    pub fn new_with_ring_buffer(port: MessagePort, ring: RingBuffer) -> Self {
+        Self {
+            port,
+            ring: Some(ring),
             _phantom: PhantomData,
         }
     }
 
     /// Sends a message to the main thread
     pub fn send(&self, event: E) {
-        self.port.post_message(&event.into()).ok();
+        let value = event.into();
+        if let Some(ring) = &self.ring {
+            let frame = js_sys::JSON::stringify(&value)
+                .ok()
+                .map(|s| String::from(s).into_bytes());
+            if let Some(frame) = frame {
+                if ring.try_write(&frame) {
+                    return;
+                }
+            }
+        }
+        self.port.post_message(&value).ok();
+    }
+
+    /// Sends a message to the main thread, transferring ownership of `transfers`
+    /// (e.g. `ArrayBuffer`s backing a wavetable or analysis buffer) instead of
+    /// structured-cloning them.
+    ///
+    /// The node side of an upload needs no counterpart in this crate: `MessagePort` is a
+    /// standard Web API already usable directly from the node's own code, worklet-bound
+    /// or not — call `port.post_message_with_transferable(&command.into(), &transfer_list)`
+    /// there the same way this method does here to hand a detached `ArrayBuffer` into a
+    /// running `AudioModule`, which arrives at `on_command` via the
+    /// `is_transferred_buffer` path below without being re-copied.
+    pub fn send_with_transfer(&self, event: E, transfers: &[JsValue]) {
+        let transfer_list = Array::new();
+        for transfer in transfers {
+            transfer_list.push(transfer);
+        }
+        self.port
+            .post_message_with_transferable(&event.into(), &transfer_list)
+            .ok();
+    }
+
+    /// Sends a correlated reply to a request previously delivered via
+    /// [`AudioModule::on_request`]. The node-side `rpc::PendingRequests` awaiting
+    /// `request_id` resolves once this reply reaches it.
+    pub fn respond(&self, request_id: u64, reply: E) {
+        // Reuse the same `InternalMessage::Reply` tagging `connect()` matches on, rather
+        // than a one-off shape - the reply payload is attached as a sibling `event`
+        // property, same as `Request`'s `command`.
+        let envelope = serde_wasm_bindgen::to_value(&InternalMessage::Reply { id: request_id })
+            .expect("InternalMessage always serializes");
+        Reflect::set(&envelope, &"event".into(), &reply.into()).ok();
+        self.port.post_message(&envelope).ok();
     }
 }
 
@@ -59,6 +120,11 @@ pub trait AudioModule {
     type Command: From<JsValue> + Into<JsValue> + FromWasmAbi = Never;
 
     /// The type of parameters used by the worklet.
+    ///
+    /// Each variant's `ParameterDescriptor` impl supplies the `defaultValue`, `minValue`,
+    /// `maxValue` and `automationRate` the Web Audio spec lets an `AudioParam` declare;
+    /// `process` receives the resulting values through `ParamBuffer`, as an a-rate
+    /// per-sample slice or a k-rate scalar depending on the descriptor.
     type Param: EnumArray<Param> + ParameterDescriptor + Debug + FromWasmAbi = Never;
 
     /// Number of inputs expected by the worklet.
@@ -73,10 +139,21 @@ pub trait AudioModule {
     /// Handler for commands from the audio node (main thread).
     fn on_command(&mut self, _command: Self::Command) {}
 
+    /// Handler for request/response style commands, i.e. commands sent via the node's
+    /// `request` API that expect a correlated reply. Implementations should answer with
+    /// `Emitter::respond(id, ..)` using the same `id`.
+    fn on_request(&mut self, _id: u64, _command: Self::Command) {}
+
     /// Implements the audio processing algorithm for the audio processor worklet.
     fn process(&mut self, audio: &mut AudioBuffer, params: &ParamBuffer<Self::Param>);
 }
 
+/// True if `data` is a detached `ArrayBuffer` or typed array received via a transfer
+/// list rather than a structured-cloned value.
+fn is_transferred_buffer(data: &JsValue) -> bool {
+    data.is_instance_of::<js_sys::ArrayBuffer>() || js_sys::ArrayBuffer::is_view(data)
+}
+
 /// Returns a float that represents the sample rate of the associated BaseAudioContext.
 pub fn sample_rate() -> f64 {
     Reflect::get(&global(), &"sampleRate".into())
@@ -114,10 +191,22 @@ pub struct Processor<M: AudioModule> {
     audio: AudioBuffer,
     params: ParamBuffer<M::Param>,
     message_callback: Option<Closure<dyn Fn(MessageEvent)>>,
+    /// Inbound command ring, polled once per render quantum when `WorkletOptions::ring_buffer` is set.
+    command_ring: Option<RingBuffer>,
 }
 
 impl<M: AudioModule + 'static> Processor<M> {
-    pub fn new(rs_processor: M, js_processor: AudioWorkletProcessor) -> Self {
+    /// Builds the `Emitter` passed to `M::create` itself (rather than accepting an
+    /// already-constructed `M`), since the emitter needs `WorkletOptions` — read here —
+    /// to know whether to wire up the outbound event ring.
+    ///
+    /// # Breaking change
+    ///
+    /// This replaces the previous two-argument `new(rs_processor: M, js_processor)`.
+    /// `waw::main!`'s generated call site isn't part of this tree snapshot; it must be
+    /// updated to stop constructing `M` itself and instead call
+    /// `Processor::<M>::new(js_processor)`, letting this constructor call `M::create`.
+    pub fn new(js_processor: AudioWorkletProcessor) -> Self {
         assert_worklet();
         // Use the js options to to allocate the buffers
         // `options` is non-standard, it's manually attached to `AudioWorkletProcessor` in the constructor.
@@ -134,6 +223,31 @@ impl<M: AudioModule + 'static> Processor<M> {
             options.output_channel_count.try_into().unwrap(),
         );
 
+        let command_ring = options
+            .ring_buffer
+            .as_ref()
+            .map(|sab| RingBuffer::new(sab, options.ring_buffer_capacity));
+
+        // Independent from `command_ring`: the worklet is the sole producer here, the
+        // node the sole consumer, same SPSC contract in the opposite direction.
+        let event_ring = options
+            .event_ring_buffer
+            .as_ref()
+            .map(|sab| RingBuffer::new(sab, options.event_ring_capacity));
+
+        let port = js_processor.port().expect("AudioWorkletProcessor has no port");
+        let emitter = match event_ring {
+            Some(ring) => Emitter::new_with_ring_buffer(port, ring),
+            None => Emitter::new(port),
+        };
+
+        let rs_processor = M::create(emitter);
+
+        if options.pool_size > 0 {
+            // Lazily spawned once and reused across render quanta; see `WorkerPool::init`.
+            WorkerPool::init(options.pool_size);
+        }
+
         Processor {
             rs_processor: Arc::new(Mutex::new(rs_processor)),
             js_processor,
@@ -141,6 +255,7 @@ impl<M: AudioModule + 'static> Processor<M> {
             audio,
             params: Default::default(),
             message_callback: None,
+            command_ring,
         }
     }
 
@@ -150,17 +265,32 @@ impl<M: AudioModule + 'static> Processor<M> {
         let rs_processor = self.rs_processor.clone();
         let enabled = self.enabled.clone();
         let callback = Closure::wrap(Box::new(move |event: MessageEvent| {
-            if let Ok(internal_message) =
-                // maybe convert this to a JS Symbol
-                serde_wasm_bindgen::from_value::<InternalMessage>(event.data())
-            {
-                match internal_message {
-                    InternalMessage::Destroy => {
-                        enabled.store(false, Ordering::Relaxed);
-                    }
-                }
+            let data = event.data();
+            // Transferred `ArrayBuffer`/typed-array payloads (wavetables, impulse
+            // responses, ...) are never internal messages; skip the probe so they reach
+            // `on_command` as-is instead of being copied for a doomed deserialize attempt.
+            let internal_message = if is_transferred_buffer(&data) {
+                None
             } else {
-                rs_processor.lock().unwrap().on_command(event.data().into());
+                serde_wasm_bindgen::from_value::<InternalMessage>(data.clone()).ok()
+            };
+
+            match internal_message {
+                Some(InternalMessage::Destroy) => {
+                    enabled.store(false, Ordering::Relaxed);
+                }
+                Some(InternalMessage::Request { id }) => {
+                    let command =
+                        Reflect::get(&data, &"command".into()).unwrap_or(JsValue::UNDEFINED);
+                    rs_processor.lock().unwrap().on_request(id, command.into());
+                }
+                Some(InternalMessage::Reply { .. }) => {
+                    // Replies only ever flow worklet -> node; the node-side consumer is
+                    // `rpc::PendingRequests::resolve`, not this (worklet-side) listener.
+                }
+                None => {
+                    rs_processor.lock().unwrap().on_command(data.into());
+                }
             }
         }) as Box<dyn Fn(MessageEvent)>);
 
@@ -180,6 +310,18 @@ impl<M: AudioModule + 'static> Processor<M> {
         self.audio.copy_from_input(input);
         self.params.copy_from_params(params);
 
+        if let Some(ring) = &self.command_ring {
+            // Ring-backed commands arrive as JSON frames; no blocking, the ring is only
+            // ever polled once per render quantum.
+            while let Some(frame) = ring.try_read() {
+                if let Ok(text) = String::from_utf8(frame) {
+                    if let Ok(value) = js_sys::JSON::parse(&text) {
+                        self.rs_processor.lock().unwrap().on_command(value.into());
+                    }
+                }
+            }
+        }
+
         self.rs_processor
             .lock()
             .unwrap()
@@ -206,12 +348,19 @@ fn js_source<M: AudioModuleDescriptor>() -> String {
         constructor(options) {{
           super();
           this.options = options;
-          const [wasm_src] = options.processorOptions || [];
-          this.init(wasm_src)
+          // `wasm_url` takes priority: it lets the module be streamed and compiled
+          // straight from the network cache instead of shipping compiled bytes through
+          // `processorOptions`.
+          const [wasm_src, wasm_url] = options.processorOptions || [];
+          this.init(wasm_src, wasm_url)
         }}
 
-        async init(wasm_src) {{
-          if (wasm_src) {{
+        async init(wasm_src, wasm_url) {{
+          if (wasm_url) {{
+            // `init` (aliased from the generated bindgen module) accepts a `Response`
+            // promise and streaming-compiles it internally via `instantiateStreaming`.
+            await globalThis.init(fetch(wasm_url));
+          }} else if (wasm_src) {{
             const module = await WebAssembly.compile(wasm_src);
             bindgen.initSync(module);
           }}